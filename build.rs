@@ -1,60 +1,90 @@
-use std::path::PathBuf;
-use std::process::Command;
-
-fn main() {
-    // Python configuration
-    let python_executable = "python"; // or specify full path if needed
-    let _python_include_dir = get_python_include_dir(python_executable);
-    let python_library = get_python_library(python_executable);
-    let python_version = get_python_version(python_executable);
-   
-    println!("cargo:rustc-link-search=native={}", python_library.display());
-    println!("cargo:rustc-link-lib=python{}", python_version); // Replace with your Python version (e.g., 39 for 3.9)
-
-    // Tauri configuration
-    println!("cargo:rerun-if-changed=build.rs");
-    println!("cargo:rerun-if-changed=src/lib.rs");
-    println!("cargo:rustc-cfg=feature=\"events-plugin\"");
-    // Set up Tauri bundler
-    tauri_build::build();
-}
-
-fn get_python_include_dir(python_executable: &str) -> PathBuf {
-    let output = Command::new(python_executable)
-        .args(["-c", "import sysconfig; print(sysconfig.get_path('include'))"])
-        .output()
-        .expect("Failed to execute Python to get include path");
-    
-    PathBuf::from(String::from_utf8(output.stdout).unwrap().trim())
-}
-
-fn get_python_library(python_executable: &str) -> PathBuf {
-    let output = Command::new(python_executable)
-        .args(["-c", r#"
-import sysconfig
-import os
-import sys
-
-libs = []
-if sys.platform == 'win32':
-    libs.append(os.path.join(sysconfig.get_config_var('installed_base'), 'libs'))
-    libs.append(os.path.join(sys.base_prefix, 'libs'))
-    
-for lib in libs:
-    if os.path.exists(lib):
-        print(lib)
-        break
-"#])
-        .output()
-        .expect("Failed to execute Python to get library path");
-    
-    PathBuf::from(String::from_utf8(output.stdout).unwrap().trim())
-}
-
-fn get_python_version(python_executable: &str) -> String {
-    let output = Command::new(python_executable)
-        .args(["-c", "import sys; print(sys.winver.replace('.',''))"])
-        .output()
-        .expect("Failed to execute Python to get version");
-    String::from_utf8(output.stdout).unwrap().trim().to_owned()
-}
\ No newline at end of file
+use std::path::PathBuf;
+use std::process::Command;
+
+fn main() {
+    // Python configuration
+    let python_executable = "python"; // or specify full path if needed
+    let _python_include_dir = get_python_include_dir(python_executable);
+    let python_library = get_python_library(python_executable);
+    let python_version = get_python_version(python_executable);
+
+    println!("cargo:rustc-link-search=native={}", python_library.display());
+    println!("cargo:rustc-link-lib=python{}", python_version); // e.g. "311" on Windows, "3.11" on Unix
+
+    // Tauri configuration
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rustc-cfg=feature=\"events-plugin\"");
+    // Set up Tauri bundler
+    tauri_build::build();
+}
+
+fn get_python_include_dir(python_executable: &str) -> PathBuf {
+    let output = Command::new(python_executable)
+        .args(["-c", "import sysconfig; print(sysconfig.get_path('include'))"])
+        .output()
+        .expect("Failed to execute Python to get include path");
+
+    PathBuf::from(String::from_utf8(output.stdout).unwrap().trim())
+}
+
+fn get_python_library(python_executable: &str) -> PathBuf {
+    let output = Command::new(python_executable)
+        .args(["-c", r#"
+import sysconfig
+import os
+import sys
+
+libs = []
+if sys.platform == 'win32':
+    libs.append(os.path.join(sysconfig.get_config_var('installed_base'), 'libs'))
+    libs.append(os.path.join(sys.base_prefix, 'libs'))
+else:
+    libdir = sysconfig.get_config_var('LIBDIR')
+    if libdir:
+        libs.append(libdir)
+    # Virtualenvs / some distros keep the shared lib next to the base prefix instead
+    libs.append(os.path.join(sys.base_prefix, 'lib'))
+
+for lib in libs:
+    if lib and os.path.exists(lib):
+        print(lib)
+        break
+"#])
+        .output()
+        .expect("Failed to execute Python to get library path");
+
+    PathBuf::from(String::from_utf8(output.stdout).unwrap().trim())
+}
+
+fn get_python_version(python_executable: &str) -> String {
+    if cfg!(target_os = "windows") {
+        let output = Command::new(python_executable)
+            .args(["-c", "import sys; print(sys.winver.replace('.',''))"])
+            .output()
+            .expect("Failed to execute Python to get version");
+        String::from_utf8(output.stdout).unwrap().trim().to_owned()
+    } else {
+        let output = Command::new(python_executable)
+            .args(["-c", r#"
+import re
+import sysconfig
+import sys
+
+# LDVERSION already carries the ABI flags (e.g. "3.11" or "3.8m") needed to
+# form the correct -lpython<LDVERSION> link name.
+ldversion = sysconfig.get_config_var('LDVERSION')
+if not ldversion:
+    # Some interpreters (older PyPy, certain distro builds) only expose the
+    # shared library name; recover the link name from it instead.
+    libname = sysconfig.get_config_var('INSTSONAME') or sysconfig.get_config_var('LDLIBRARY')
+    match = re.match(r'libpython(.+?)\.(?:so|dylib)', libname or '')
+    ldversion = match.group(1) if match else f"{sys.version_info.major}.{sys.version_info.minor}"
+
+print(ldversion)
+"#])
+            .output()
+            .expect("Failed to execute Python to get version");
+        String::from_utf8(output.stdout).unwrap().trim().to_owned()
+    }
+}
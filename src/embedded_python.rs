@@ -0,0 +1,62 @@
+//! Opt-in support for shipping a bundled Python runtime as a Tauri resource,
+//! so a packaged app works without relying on the end user's system Python.
+//! Only compiled in when the `embedded-python` feature is enabled.
+//!
+//! This crate is a `#[pymodule]`: it is loaded *into* an already-running
+//! interpreter rather than embedding one itself, so by the time `configure`
+//! runs, `Py_Initialize` has long since resolved and loaded the stdlib from
+//! wherever the host interpreter's own `sys.prefix` pointed at startup.
+//! Rewriting `sys.prefix` afterward cannot relocate that. Only `sys.path`
+//! (consulted lazily on every future import) can meaningfully be redirected
+//! here, so `python_home` is accepted for API symmetry but is never applied;
+//! the bundled runtime is only used if the app is launched with `PYTHONHOME`
+//! already pointed at it before the interpreter starts (e.g. by a thin
+//! native launcher).
+//!
+//! **Scope note for whoever filed the original request:** the ask was for a
+//! genuinely self-contained packaged app — an isolated interpreter whose
+//! home/prefix and `sys.path` point entirely at a bundled runtime, the way
+//! `pyembed` configures one from scratch before any import happens. That is
+//! not what this delivers, and cannot be delivered from inside a
+//! `#[pymodule]`: this subsystem only redirects future imports via
+//! `sys.path`. A packaged `.app`/`.msi` built with this feature still
+//! depends on *something* having already initialized a Python interpreter
+//! with `PYTHONHOME` pointed at the bundled runtime first (e.g. a thin
+//! native launcher), which is the part that actually makes the app
+//! interpreter-independent. Genuinely closing that gap means embedding the
+//! interpreter ourselves (`pyo3::prepare_freethreaded_python` plus owning
+//! `Py_Initialize`/`Py_SetPythonHome`) instead of being loaded into one.
+
+use pyo3::prelude::*;
+use pyo3::types::PyList;
+use std::path::Path;
+
+/// Resolves `TauriApp.set_module_search_paths` entries against the app's
+/// resource directory and prepends them to `sys.path`. `python_home` cannot
+/// be applied post-init (see module docs) and is only used to warn the
+/// developer that it had no effect.
+pub fn configure(
+    py: Python,
+    resource_dir: &Path,
+    python_home: Option<String>,
+    module_search_paths: Vec<String>,
+) -> PyResult<()> {
+    if python_home.is_some() {
+        log::warn!(
+            "TauriApp.set_python_home has no effect: the interpreter is already initialized \
+             by the time this pymodule runs. Set PYTHONHOME before launching the app instead."
+        );
+    }
+
+    if !module_search_paths.is_empty() {
+        let sys = py.import("sys")?;
+        let sys_path = sys.getattr("path")?;
+        let sys_path = sys_path.downcast::<PyList>()?;
+        for (index, relative) in module_search_paths.iter().enumerate() {
+            let absolute = resource_dir.join(relative);
+            sys_path.insert(index, absolute.to_string_lossy().to_string())?;
+        }
+    }
+
+    Ok(())
+}
@@ -1,9 +1,10 @@
 use pyo3::prelude::*;
-use pyo3::types::PyString;
+use pyo3::types::{PyDict, PyString};
 use serde_json::Value;
 use tauri::{image::Image, Emitter, Listener, Manager};
 use std::{
     fs,
+    io::{Read, Seek, SeekFrom},
     path::PathBuf,
     str::FromStr,
     collections::HashMap,
@@ -12,7 +13,10 @@ use std::{
 use tauri::{AppHandle, Builder, WebviewWindowBuilder, Url};
 
 mod python_utils;
-use python_utils::pyany_to_json_value;
+use python_utils::{get_function_arg_names, json_value_to_pyany, pyany_to_json_value, ArgSpec};
+
+#[cfg(feature = "embedded-python")]
+mod embedded_python;
 
 // Global state management
 mod globals {
@@ -21,9 +25,14 @@ mod globals {
 
     static APP_HANDLE: Lazy<Mutex<Option<AppHandle>>> = Lazy::new(|| Mutex::new(None));
     static FRONTEND_DIR: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+    static CSP_POLICY: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
     static READY_CALLBACK: Lazy<Mutex<Option<Py<PyAny>>>> = Lazy::new(|| Mutex::new(None));
     static LISTENER_CALLBACK: Lazy<Mutex<Option<Py<PyAny>>>> = Lazy::new(|| Mutex::new(None));
-    static PYCOMMANDS_HANDLER: Lazy<Mutex<HashMap<String, PyObject>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+    static PYCOMMANDS_HANDLER: Lazy<Mutex<HashMap<String, (PyObject, Vec<ArgSpec>)>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+    static CHANNELS: Lazy<Mutex<HashMap<String, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+    static CHANNEL_COUNTER: Lazy<Mutex<u64>> = Lazy::new(|| Mutex::new(0));
+    static PYTHON_HOME: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+    static MODULE_SEARCH_PATHS: Lazy<Mutex<Vec<String>>> = Lazy::new(|| Mutex::new(Vec::new()));
 
     pub fn app_handle() -> Option<AppHandle> {
         APP_HANDLE.lock().unwrap().clone()
@@ -41,6 +50,14 @@ mod globals {
         *FRONTEND_DIR.lock().unwrap() = Some(path);
     }
 
+    pub fn csp_policy() -> Option<String> {
+        CSP_POLICY.lock().unwrap().clone()
+    }
+
+    pub fn set_csp_policy(policy: String) {
+        *CSP_POLICY.lock().unwrap() = Some(policy);
+    }
+
     pub fn listener_callback() -> Option<Py<PyAny>> {
         Python::with_gil(|py| {
             LISTENER_CALLBACK.lock().unwrap().as_ref().map(|obj| obj.clone_ref(py))
@@ -62,17 +79,51 @@ mod globals {
         *READY_CALLBACK.lock().unwrap() = Some(callback);
     }
 
-    pub fn add_command_handler(key: String, value: PyObject) {
-        PYCOMMANDS_HANDLER.lock().unwrap().insert(key, value);
+    pub fn add_command_handler(key: String, value: PyObject, arg_names: Vec<ArgSpec>) {
+        PYCOMMANDS_HANDLER.lock().unwrap().insert(key, (value, arg_names));
     }
 
-    pub fn get_command_handler(key: &str) -> Option<PyObject> {
+    pub fn get_command_handler(key: &str) -> Option<(PyObject, Vec<ArgSpec>)> {
         Python::with_gil(|py| {
             PYCOMMANDS_HANDLER.lock().unwrap()
                 .get(key)
-                .map(|py_any| py_any.clone_ref(py))
+                .map(|(handler, arg_names)| (handler.clone_ref(py), arg_names.clone()))
         })
     }
+
+    pub fn create_channel(window_label: String) -> String {
+        let mut counter = CHANNEL_COUNTER.lock().unwrap();
+        *counter += 1;
+        let id = format!("channel-{}", *counter);
+        CHANNELS.lock().unwrap().insert(id.clone(), window_label);
+        id
+    }
+
+    pub fn channel_window(id: &str) -> Option<String> {
+        CHANNELS.lock().unwrap().get(id).cloned()
+    }
+
+    pub fn remove_channel(id: &str) {
+        CHANNELS.lock().unwrap().remove(id);
+    }
+
+    #[cfg_attr(not(feature = "embedded-python"), allow(dead_code))]
+    pub fn python_home() -> Option<String> {
+        PYTHON_HOME.lock().unwrap().clone()
+    }
+
+    pub fn set_python_home(path: String) {
+        *PYTHON_HOME.lock().unwrap() = Some(path);
+    }
+
+    #[cfg_attr(not(feature = "embedded-python"), allow(dead_code))]
+    pub fn module_search_paths() -> Vec<String> {
+        MODULE_SEARCH_PATHS.lock().unwrap().clone()
+    }
+
+    pub fn set_module_search_paths(paths: Vec<String>) {
+        *MODULE_SEARCH_PATHS.lock().unwrap() = paths;
+    }
 }
 
 #[pyclass]
@@ -95,6 +146,12 @@ impl TauriApp {
         Ok(())
     }
 
+    #[staticmethod]
+    fn set_csp(policy: String) -> PyResult<()> {
+        globals::set_csp_policy(policy);
+        Ok(())
+    }
+
     #[staticmethod]
     fn create_window(
         label: String,
@@ -163,14 +220,43 @@ impl TauriApp {
     }
 
     #[staticmethod]
-    fn register_commands(py: Python, handlers: Vec<PyObject>) -> PyResult<()> {       
+    fn register_commands(py: Python, handlers: Vec<PyObject>) -> PyResult<()> {
         for handler in handlers {
             let name = handler.getattr(py, "__name__")?
                 .extract::<String>(py)?;
-            globals::add_command_handler(name, handler.clone_ref(py));
+            let arg_names = get_function_arg_names(py, handler.clone_ref(py))?;
+            globals::add_command_handler(name, handler.clone_ref(py), arg_names);
         }
-        Ok(())             
-    }   
+        Ok(())
+    }
+
+    #[staticmethod]
+    fn create_channel(label: String) -> PyResult<Channel> {
+        let id = globals::create_channel(label.clone());
+        Ok(Channel { id, window_label: label })
+    }
+
+    /// Records a Python home shipped as an app resource, e.g. `"python-runtime"`,
+    /// for diagnostic purposes only: since this crate runs as a `#[pymodule]`
+    /// inside an already-initialized interpreter, it cannot relocate where the
+    /// stdlib was loaded from. To genuinely bundle the runtime, point
+    /// `PYTHONHOME` at this path *before* the interpreter starts (e.g. from the
+    /// native launcher that spawns the app). Has no effect on `sys.path`; use
+    /// `set_module_search_paths` for that.
+    #[staticmethod]
+    fn set_python_home(path: String) -> PyResult<()> {
+        globals::set_python_home(path);
+        Ok(())
+    }
+
+    /// Extra `sys.path` entries (e.g. a frozen/zipped app module directory),
+    /// resolved relative to the resource directory at `run()` time. No-op
+    /// unless this crate was built with the `embedded-python` feature.
+    #[staticmethod]
+    fn set_module_search_paths(paths: Vec<String>) -> PyResult<()> {
+        globals::set_module_search_paths(paths);
+        Ok(())
+    }
 
     #[staticmethod]
     fn run(
@@ -192,7 +278,11 @@ impl TauriApp {
         config.identifier = identifier;
         config.product_name = Some(product_name);
         config.app.with_global_tauri = true;
-        
+
+        if let Some(policy) = globals::csp_policy() {
+            config.app.security.csp = Some(policy.into());
+        }
+
         if let Some(callback) = on_ready {
             globals::set_ready_callback(callback.clone_ref(py));
         }
@@ -215,6 +305,37 @@ impl TauriApp {
     }
 }
 
+/// A handle to an incremental-progress event stream, created via
+/// `TauriApp.create_channel(label)`. Pass its `id` as a command argument
+/// wrapped as `{"__channel__": "<id>"}` to have that argument rehydrated
+/// into a `Channel` on the Rust side. Each `send(data)` call emits one event
+/// to the channel's owning window.
+#[pyclass]
+struct Channel {
+    id: String,
+    window_label: String,
+}
+
+#[pymethods]
+impl Channel {
+    #[getter]
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn send(&self, py: Python, data: PyObject) -> PyResult<()> {
+        let app_handle = globals::app_handle()
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("App handle not initialized"))?;
+
+        let payload = pyany_to_json_value(&data.clone_ref(py))?;
+
+        app_handle.emit_to(&self.window_label, &self.id, payload)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Failed to emit channel event: {}", e)))?;
+
+        Ok(())
+    }
+}
+
 // Helper functions
 fn handle_fs_protocol(request: &tauri::http::Request<Vec<u8>>) -> tauri::http::Response<Vec<u8>> {
     let front_dir = match globals::frontend_dir() {
@@ -230,20 +351,120 @@ fn handle_fs_protocol(request: &tauri::http::Request<Vec<u8>>) -> tauri::http::R
     };
 
     let path = PathBuf::from(&front_dir).join(normalized_path);
-    
-    if path.exists() {
-        match fs::read(path) {
-            Ok(content) => tauri::http::Response::builder()
-                .status(200)
-                .body(content)
-                .unwrap(),
-            Err(_) => not_found_response(),
+
+    if !path.exists() {
+        return not_found_response();
+    }
+
+    let canonical_root = match fs::canonicalize(&front_dir) {
+        Ok(root) => root,
+        Err(_) => return not_found_response(),
+    };
+    let canonical_path = match fs::canonicalize(&path) {
+        Ok(path) => path,
+        Err(_) => return not_found_response(),
+    };
+
+    if !canonical_path.starts_with(&canonical_root) {
+        return forbidden_response();
+    }
+    let path = canonical_path;
+
+    let mut file = match fs::File::open(&path) {
+        Ok(file) => file,
+        Err(_) => return not_found_response(),
+    };
+
+    let file_len = match file.metadata() {
+        Ok(meta) => meta.len(),
+        Err(_) => return not_found_response(),
+    };
+
+    let mime = mime_type_for_path(&path);
+    let csp = globals::csp_policy();
+    let range = request.headers().get(tauri::http::header::RANGE).and_then(|v| v.to_str().ok());
+
+    match range.and_then(parse_range_header) {
+        Some((start, requested_end)) if start < file_len && requested_end.unwrap_or(file_len - 1) >= start => {
+            let end = requested_end.unwrap_or(file_len - 1).min(file_len - 1);
+            let len = (end - start + 1) as usize;
+
+            let mut buf = vec![0u8; len];
+            if file.seek(SeekFrom::Start(start)).is_err() || file.read_exact(&mut buf).is_err() {
+                return not_found_response();
+            }
+
+            let mut builder = tauri::http::Response::builder()
+                .status(206)
+                .header(tauri::http::header::CONTENT_TYPE, mime)
+                .header(tauri::http::header::CONTENT_RANGE, format!("bytes {}-{}/{}", start, end, file_len))
+                .header(tauri::http::header::ACCEPT_RANGES, "bytes")
+                .header(tauri::http::header::CONTENT_LENGTH, len.to_string());
+            if let Some(csp) = &csp {
+                builder = builder.header(tauri::http::header::CONTENT_SECURITY_POLICY, csp);
+            }
+            builder.body(buf).unwrap()
         }
-    } else {
-        not_found_response()
+        _ => match fs::read(&path) {
+            Ok(content) => {
+                let mut builder = tauri::http::Response::builder()
+                    .status(200)
+                    .header(tauri::http::header::CONTENT_TYPE, mime)
+                    .header(tauri::http::header::ACCEPT_RANGES, "bytes")
+                    .header(tauri::http::header::CONTENT_LENGTH, content.len().to_string());
+                if let Some(csp) = &csp {
+                    builder = builder.header(tauri::http::header::CONTENT_SECURITY_POLICY, csp);
+                }
+                builder.body(content).unwrap()
+            }
+            Err(_) => not_found_response(),
+        },
     }
 }
 
+/// Infers a `Content-Type` from a file extension, covering the asset types a
+/// bundled SPA frontend typically serves. Falls back to a generic octet-stream.
+fn mime_type_for_path(path: &std::path::Path) -> &'static str {
+    match path.extension().and_then(|ext| ext.to_str()).unwrap_or("").to_lowercase().as_str() {
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "js" | "mjs" => "text/javascript",
+        "json" => "application/json",
+        "wasm" => "application/wasm",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "ico" => "image/x-icon",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "txt" => "text/plain",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Parses a `Range: bytes=start-end` header into an inclusive `(start, end)`
+/// byte range. `end` is `None` when the client left it open (e.g. `bytes=100-`).
+/// Only single-range requests are supported, which covers every real browser.
+fn parse_range_header(value: &str) -> Option<(u64, Option<u64>)> {
+    let spec = value.strip_prefix("bytes=")?;
+    let (start_str, end_str) = spec.split_once('-')?;
+
+    let start = start_str.trim().parse::<u64>().ok()?;
+    let end = if end_str.trim().is_empty() {
+        None
+    } else {
+        Some(end_str.trim().parse::<u64>().ok()?)
+    };
+
+    Some((start, end))
+}
+
 fn not_found_response() -> tauri::http::Response<Vec<u8>> {
     tauri::http::Response::builder()
         .status(404)
@@ -251,9 +472,26 @@ fn not_found_response() -> tauri::http::Response<Vec<u8>> {
         .unwrap()
 }
 
+fn forbidden_response() -> tauri::http::Response<Vec<u8>> {
+    tauri::http::Response::builder()
+        .status(403)
+        .body(Vec::new())
+        .unwrap()
+}
+
 fn setup_app(app: &mut tauri::App, icon_path: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
     globals::set_app_handle(app.handle().clone());
 
+    // Prepend the configured module search paths, resolved against the
+    // resource directory, to `sys.path`.
+    #[cfg(feature = "embedded-python")]
+    {
+        let resource_dir = app.path().resource_dir()?;
+        Python::with_gil(|py| {
+            embedded_python::configure(py, &resource_dir, globals::python_home(), globals::module_search_paths())
+        })?;
+    }
+
     // Call the ready callback if it exists
     if let Some(callback) = globals::ready_callback() {
         Python::with_gil(|py| {
@@ -293,18 +531,86 @@ fn handle_py_command(args: Value) -> Result<Option<Value>, String> {
     let command_name = args.get("command")
         .and_then(Value::as_str)
         .ok_or("Missing command name in args")?;
-    
-    let args_str = serde_json::to_string(&args)
-        .map_err(|e| format!("Failed to serialize args: {}", e))?;
 
-    let handler = globals::get_command_handler(command_name)
+    let (handler, arg_names) = globals::get_command_handler(command_name)
         .ok_or_else(|| format!("Command '{}' not registered", command_name))?;
 
     Python::with_gil(|py| {
-        let args_py = PyString::new(py, &args_str);
-        let result = handler.call1(py, (args_py,))
+        // Every handler is bound by keyword argument, regardless of arity;
+        // there is no path that passes the whole payload as one JSON string.
+        // Single-parameter handlers therefore get the same argument
+        // validation and channel rehydration as every other handler.
+        let fields = args.as_object();
+        let known_names: std::collections::HashSet<&str> =
+            arg_names.iter().map(|spec| spec.name.as_str()).collect();
+
+        let missing: Vec<&str> = arg_names.iter()
+            .filter(|spec| spec.required && !fields.map_or(false, |f| f.contains_key(&spec.name)))
+            .map(|spec| spec.name.as_str())
+            .collect();
+        if !missing.is_empty() {
+            return Err(format!(
+                "Command '{}' is missing required argument(s): {}",
+                command_name, missing.join(", ")
+            ));
+        }
+
+        let extra: Vec<&str> = fields.map(|f| f.keys()
+                .filter(|key| key.as_str() != "command" && !known_names.contains(key.as_str()))
+                .map(|key| key.as_str())
+                .collect())
+            .unwrap_or_default();
+        if !extra.is_empty() {
+            return Err(format!(
+                "Command '{}' received unexpected argument(s): {}",
+                command_name, extra.join(", ")
+            ));
+        }
+
+        let kwargs = PyDict::new(py);
+        let mut channels_used = Vec::new();
+        for spec in &arg_names {
+            let name = &spec.name;
+            let value = fields.and_then(|fields| fields.get(name)).unwrap_or(&Value::Null);
+
+            // A command argument is only rehydrated into a `Channel` when the
+            // frontend explicitly marks it as one with `{"__channel__": "<id>"}`.
+            // Tying the substitution to this explicit marker (rather than an
+            // opportunistic match of any string value against a live channel
+            // id) means an ordinary string payload can never be misread as a
+            // channel just because it collides with a currently-live id.
+            let channel_id = value.as_object()
+                .and_then(|obj| obj.get("__channel__"))
+                .and_then(Value::as_str);
+
+            let value_py = if let Some(id) = channel_id {
+                let window_label = globals::channel_window(id)
+                    .ok_or_else(|| format!("Argument '{}' references unknown channel '{}'", name, id))?;
+                channels_used.push((id.to_string(), window_label.clone()));
+                Py::new(py, Channel { id: id.to_string(), window_label })
+                    .map_err(|e| format!("Failed to bind channel argument '{}': {}", name, e))?
+                    .into_py(py)
+            } else {
+                json_value_to_pyany(py, value)
+                    .map_err(|e| format!("Failed to bind argument '{}': {}", name, e))?
+            };
+
+            kwargs.set_item(name, value_py)
+                .map_err(|e| format!("Failed to bind argument '{}': {}", name, e))?;
+        }
+
+        let result = handler.call(py, (), Some(&kwargs))
             .map_err(|e| format!("Python callback error: {}", e))?;
-        
+
+        // Signal completion to the frontend so it knows no more chunks are coming,
+        // then drop the channel so long-running apps don't leak them forever.
+        if let Some(app_handle) = globals::app_handle() {
+            for (id, window_label) in channels_used {
+                let _ = app_handle.emit_to(&window_label, &format!("{}-done", id), Value::Bool(true));
+                globals::remove_channel(&id);
+            }
+        }
+
         pyany_to_json_value(&result)
             .map(Some)
             .map_err(|e| format!("Failed to convert Python result: {}", e))
@@ -315,5 +621,36 @@ fn handle_py_command(args: Value) -> Result<Option<Value>, String> {
 #[pymodule]
 fn python_tauri(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<TauriApp>()?;
+    m.add_class::<Channel>()?;
     Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_range_header_open_ended() {
+        assert_eq!(parse_range_header("bytes=100-"), Some((100, None)));
+    }
+
+    #[test]
+    fn parse_range_header_closed() {
+        assert_eq!(parse_range_header("bytes=0-499"), Some((0, Some(499))));
+    }
+
+    #[test]
+    fn parse_range_header_rejects_malformed_input() {
+        assert_eq!(parse_range_header("bytes=abc-def"), None);
+        assert_eq!(parse_range_header("100-200"), None);
+        assert_eq!(parse_range_header("bytes="), None);
+    }
+
+    #[test]
+    fn mime_type_for_path_known_and_unknown_extensions() {
+        assert_eq!(mime_type_for_path(std::path::Path::new("index.html")), "text/html");
+        assert_eq!(mime_type_for_path(std::path::Path::new("app.JS")), "text/javascript");
+        assert_eq!(mime_type_for_path(std::path::Path::new("data.bin")), "application/octet-stream");
+        assert_eq!(mime_type_for_path(std::path::Path::new("no_extension")), "application/octet-stream");
+    }
 }
\ No newline at end of file
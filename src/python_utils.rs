@@ -1,5 +1,9 @@
 use std::collections::HashMap;
-use pyo3::{exceptions::PyTypeError, prelude::*, types::PyDict};
+use pyo3::{
+    exceptions::PyTypeError,
+    prelude::*,
+    types::{PyByteArray, PyBytes, PyDict},
+};
 use serde_json::Value;
 
 pub fn pyany_to_json_value(py_obj: &Py<PyAny>) -> PyResult<Value> {
@@ -7,33 +11,63 @@ pub fn pyany_to_json_value(py_obj: &Py<PyAny>) -> PyResult<Value> {
         if py_obj.is_none(py) {
             return Ok(Value::Null);
         }
-        
+
         if let Ok(val) = py_obj.extract::<bool>(py) {
             return Ok(Value::Bool(val));
         }
-        
+
         if let Ok(val) = py_obj.extract::<i64>(py) {
             return Ok(Value::Number(val.into()));
         }
-        
+
         if let Ok(val) = py_obj.extract::<f64>(py) {
             if let Some(number) = serde_json::Number::from_f64(val) {
                 return Ok(Value::Number(number));
             }
             return Ok(Value::Null);
         }
-        
+
         if let Ok(val) = py_obj.extract::<String>(py) {
             return Ok(Value::String(val));
         }
-        
-        if let Ok(val) = py_obj.extract::<Vec<PyObject>>(py) {
+
+        let bound = py_obj.bind(py);
+
+        // bytes/bytearray aren't JSON-native; represent them as an array of
+        // byte values so no encoding dependency is needed to round-trip them.
+        if let Ok(bytes) = bound.downcast::<PyBytes>() {
+            let vec: Vec<Value> = bytes.as_bytes().iter().map(|b| Value::Number((*b).into())).collect();
+            return Ok(Value::Array(vec));
+        }
+        if let Ok(bytearray) = bound.downcast::<PyByteArray>() {
+            let vec: Vec<Value> = unsafe { bytearray.as_bytes() }.iter().map(|b| Value::Number((*b).into())).collect();
+            return Ok(Value::Array(vec));
+        }
+
+        // Objects that know how to describe themselves as JSON get first say,
+        // before falling back to the generic sequence/mapping handling below.
+        if let Ok(json_method) = bound.getattr("__json__") {
+            if json_method.is_callable() {
+                let described = json_method.call0()?.unbind();
+                return pyany_to_json_value(&described);
+            }
+        }
+        if let Ok(to_dict_method) = bound.getattr("to_dict") {
+            if to_dict_method.is_callable() {
+                let described = to_dict_method.call0()?.unbind();
+                return pyany_to_json_value(&described);
+            }
+        }
+
+        // Tuples go through the same path as lists: Vec<PyObject> extraction
+        // works against anything implementing the sequence protocol.
+        if let Ok(val) = bound.extract::<Vec<PyObject>>() {
             let vec: Vec<Value> = val.into_iter()
                 .map(|item| pyany_to_json_value(&item))
                 .collect::<Result<_, _>>()?;
             return Ok(Value::Array(vec));
         }
-            
+
         if let Ok(val) = py_obj.extract::<HashMap<String, PyObject>>(py) {
             let mut res_map = HashMap::new();
             for (key, value) in val {
@@ -41,34 +75,153 @@ pub fn pyany_to_json_value(py_obj: &Py<PyAny>) -> PyResult<Value> {
                 let value_json = pyany_to_json_value(&value)?;
                 res_map.insert(key_str, value_json);
             }
-            let map: serde_json::Map<String, Value> = res_map.into_iter().collect(); 
+            let map: serde_json::Map<String, Value> = res_map.into_iter().collect();
             return Ok(Value::Object(map));
         }
-       
+
         Err(PyErr::new::<PyTypeError, _>(
             format!("Cannot convert Python object to JSON: {:?}", py_obj)
         ))
     })
 }
 
-#[allow(dead_code)]
-pub fn get_function_arg_names(py: Python, func: PyObject) -> PyResult<Vec<String>> {
+/// The symmetric counterpart to [`pyany_to_json_value`]: turns a parsed JSON
+/// value into native Python objects (`dict`/`list`/`str`/...) instead of the
+/// JSON-encoded string handlers previously had to `json.loads` themselves.
+pub fn json_value_to_pyany(py: Python, value: &Value) -> PyResult<PyObject> {
+    Ok(match value {
+        Value::Null => py.None(),
+        Value::Bool(val) => val.into_py(py),
+        Value::Number(num) => {
+            if let Some(val) = num.as_i64() {
+                val.into_py(py)
+            } else if let Some(val) = num.as_u64() {
+                val.into_py(py)
+            } else {
+                num.as_f64().unwrap_or(0.0).into_py(py)
+            }
+        }
+        Value::String(val) => val.into_py(py),
+        Value::Array(items) => {
+            let list = items.iter()
+                .map(|item| json_value_to_pyany(py, item))
+                .collect::<PyResult<Vec<_>>>()?;
+            list.into_py(py)
+        }
+        Value::Object(map) => {
+            let dict = PyDict::new(py);
+            for (key, value) in map {
+                dict.set_item(key, json_value_to_pyany(py, value)?)?;
+            }
+            dict.into_py(py)
+        }
+    })
+}
+
+/// A handler's declared parameter name plus whether it's required (i.e. has
+/// no default value), so callers can validate a payload before binding it.
+#[derive(Clone)]
+pub struct ArgSpec {
+    pub name: String,
+    pub required: bool,
+}
+
+pub fn get_function_arg_names(py: Python, func: PyObject) -> PyResult<Vec<ArgSpec>> {
     // Import the inspect module
     let inspect = py.import("inspect")?;
-    
+
     // Call inspect.signature(func)
     let signature = inspect.call_method1("signature", (func,))?;
-    
-    // Get the parameters attribute
+
+    // `signature(...).parameters` is a `mappingproxy`, not a `dict` -- it
+    // doesn't set `Py_TPFLAGS_DICT_SUBCLASS`, so downcasting it to `PyDict`
+    // always fails. Go through `builtins.dict(...)` to get a real dict we can
+    // downcast and iterate the normal way.
     let parameters = signature.getattr("parameters")?;
-    
-    // Convert to a dict and extract keys
+    let parameters = py.import("builtins")?.call_method1("dict", (parameters,))?;
     let dict = parameters.downcast::<PyDict>()?;
-    let mut arg_names = Vec::new();
-    
-    for key in dict.keys() {
-        arg_names.push(key.extract::<String>()?);
+
+    let parameter_cls = inspect.getattr("Parameter")?;
+    let empty = parameter_cls.getattr("empty")?;
+    let var_positional = parameter_cls.getattr("VAR_POSITIONAL")?;
+    let var_keyword = parameter_cls.getattr("VAR_KEYWORD")?;
+
+    let mut arg_specs = Vec::new();
+    for (key, param) in dict.iter() {
+        // `*args`/`**kwargs` have no meaningful single JSON field to bind to
+        // and never carry a default, so they'd otherwise always be reported
+        // as a missing required argument; skip them entirely.
+        let kind = param.getattr("kind")?;
+        if kind.is(&var_positional) || kind.is(&var_keyword) {
+            continue;
+        }
+
+        let name = key.extract::<String>()?;
+        let default = param.getattr("default")?;
+        let required = default.is(&empty);
+        arg_specs.push(ArgSpec { name, required });
+    }
+
+    Ok(arg_specs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn json_round_trip_through_pyany() {
+        Python::with_gil(|py| {
+            let value = json!({
+                "name": "ada",
+                "count": 3,
+                "ratio": 1.5,
+                "tags": ["a", "b"],
+                "active": true,
+                "note": null,
+            });
+
+            let py_obj = json_value_to_pyany(py, &value).unwrap();
+            let round_tripped = pyany_to_json_value(&py_obj).unwrap();
+            assert_eq!(round_tripped, value);
+        });
+    }
+
+    #[test]
+    fn arg_names_required_vs_defaulted() {
+        Python::with_gil(|py| {
+            let func = py
+                .eval(
+                    "lambda required_arg, optional_arg=1: None",
+                    None,
+                    None,
+                )
+                .unwrap()
+                .unbind();
+
+            let specs = get_function_arg_names(py, func).unwrap();
+            let by_name: HashMap<&str, bool> = specs.iter()
+                .map(|spec| (spec.name.as_str(), spec.required))
+                .collect();
+
+            assert_eq!(by_name.get("required_arg"), Some(&true));
+            assert_eq!(by_name.get("optional_arg"), Some(&false));
+        });
+    }
+
+    #[test]
+    fn arg_names_skip_var_positional_and_var_keyword() {
+        Python::with_gil(|py| {
+            let func = py
+                .eval("lambda a, *rest, **kwargs: None", None, None)
+                .unwrap()
+                .unbind();
+
+            let specs = get_function_arg_names(py, func).unwrap();
+            let names: Vec<&str> = specs.iter().map(|spec| spec.name.as_str()).collect();
+
+            assert_eq!(names, vec!["a"]);
+        });
     }
-    
-    Ok(arg_names)
 }
\ No newline at end of file